@@ -69,6 +69,148 @@ where
     result
 }
 
+/// Variant of [`distinct_permutations_with`] that stops descending once `head`
+/// has reached length `k`, yielding length-`k` arrangements rather than only
+/// full-length ones. Duplicate prefixes are still skipped via `Counts`.
+fn distinct_permutations_k_with<T>(
+    head: &mut Vec<T>,
+    counts: &mut Counts<T>,
+    k: usize,
+) -> Vec<Vec<T>>
+where
+    T: Eq + Hash + Clone + Ord,
+{
+    if head.len() == k {
+        return vec![head.clone()];
+    }
+    let mut result = vec![];
+    let mut keys = counts.keys();
+    keys.sort();
+    for value in keys {
+        head.push(value.clone());
+        counts.remove(&value);
+        result.append(&mut distinct_permutations_k_with(head, counts, k));
+        head.pop();
+        counts.add(value);
+    }
+    result
+}
+
+/// Returns the distinct length-`k` arrangements of `input`, lexicographically
+/// sorted, mirroring `itertools`' `permutations(k)` but collapsing arrangements
+/// that are equal with respect to `Eq` on `T`.
+///
+/// As with [`distinct_permutations`], the runtime is proportional to the size of
+/// the output generated, not the number of arrangements ignoring equality.
+///
+///# Examples
+/// ```rust
+/// # use distinct_permutations::distinct_permutations_k;
+/// assert_eq!(distinct_permutations_k(vec![0, 0, 1], 2),
+///     vec![
+///         vec![0, 0],
+///         vec![0, 1],
+///         vec![1, 0]
+/// ]);
+/// ```
+pub fn distinct_permutations_k<T>(input: Vec<T>, k: usize) -> Vec<Vec<T>>
+where
+    T: Eq + Hash + Clone + Ord,
+{
+    let mut counts = Counts::from(input.into_iter().counts());
+    let mut head = Vec::with_capacity(k);
+    distinct_permutations_k_with(&mut head, &mut counts, k)
+}
+
+/// Lazy iterator over the distinct length-`k` arrangements of a multiset in
+/// lexicographic order, created by [`distinct_permutations_k_iter`].
+///
+/// It walks the same recursion tree as [`distinct_permutations_k`] with an
+/// explicit stack, so only the current prefix (and the per-depth choices) are
+/// held in memory.
+pub struct DistinctPermutationsK<T>
+where
+    T: Eq + Hash + Clone + Ord,
+{
+    head: Vec<T>,
+    counts: Counts<T>,
+    k: usize,
+    /// One entry per open depth: the sorted candidate symbols and the next one
+    /// to try.
+    stack: Vec<(Vec<T>, usize)>,
+    /// Set for the `k == 0` case, whose sole arrangement is the empty one.
+    empty_pending: bool,
+}
+
+impl<T> Iterator for DistinctPermutationsK<T>
+where
+    T: Eq + Hash + Clone + Ord,
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.empty_pending {
+            self.empty_pending = false;
+            return Some(vec![]);
+        }
+        loop {
+            let (keys, pos) = self.stack.last_mut()?;
+            if *pos < keys.len() {
+                let value = keys[*pos].clone();
+                *pos += 1;
+                self.head.push(value.clone());
+                self.counts.remove(&value);
+                if self.head.len() == self.k {
+                    let leaf = self.head.clone();
+                    self.head.pop();
+                    self.counts.add(value);
+                    return Some(leaf);
+                }
+                let mut next_keys = self.counts.keys();
+                next_keys.sort();
+                self.stack.push((next_keys, 0));
+            } else {
+                self.stack.pop();
+                if let Some(value) = self.head.pop() {
+                    self.counts.add(value);
+                }
+            }
+        }
+    }
+}
+
+/// Returns a [`DistinctPermutationsK`] iterator yielding the same lexicographic
+/// sequence as [`distinct_permutations_k`], but one arrangement at a time.
+///
+///# Examples
+/// ```rust
+/// # use distinct_permutations::distinct_permutations_k_iter;
+/// assert_eq!(distinct_permutations_k_iter(vec![0, 0, 1], 2).collect::<Vec<_>>(),
+///     vec![vec![0, 0], vec![0, 1], vec![1, 0]]);
+/// ```
+pub fn distinct_permutations_k_iter<T>(input: Vec<T>, k: usize) -> DistinctPermutationsK<T>
+where
+    T: Eq + Hash + Clone + Ord,
+{
+    let counts = Counts::from(input.into_iter().counts());
+    // For `k == 0` the sole arrangement is the empty one; leaving the stack
+    // empty makes the iterator O(1) instead of walking the whole recursion tree.
+    let stack = if k == 0 {
+        vec![]
+    } else {
+        let mut keys = counts.keys();
+        keys.sort();
+        vec![(keys, 0)]
+    };
+    DistinctPermutationsK {
+        head: Vec::with_capacity(k),
+        counts,
+        k,
+        stack,
+        empty_pending: k == 0,
+    }
+}
+
 /// Returns the permutations of the input vector that are distinct with
 /// respect to `Eq` on `T`, lexicographically sorted.
 ///
@@ -97,6 +239,366 @@ where
     distinct_permutations_with(&mut head, &mut counts)
 }
 
+/// Lazy iterator over the distinct permutations of a multiset in lexicographic
+/// order, holding only the current permutation in memory.
+///
+/// Created by [`distinct_permutations_iter`]. Unlike [`distinct_permutations`],
+/// which materializes the whole `Vec<Vec<T>>`, this advances one permutation at
+/// a time and supports early termination (e.g. `.take(1000)`).
+pub struct DistinctPermutations<T>
+where
+    T: Ord + Clone,
+{
+    current: Option<Vec<T>>,
+}
+
+impl<T> Iterator for DistinctPermutations<T>
+where
+    T: Ord + Clone,
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let current = self.current.take()?;
+        // Prepare the successor before handing out the current permutation, so
+        // that the struct only ever retains a single `Vec<T>`.
+        let mut successor = current.clone();
+        if successor.next_distinct_permutation() {
+            self.current = Some(successor);
+        }
+        Some(current)
+    }
+}
+
+/// Returns a [`DistinctPermutations`] iterator yielding the same lexicographic
+/// sequence as [`distinct_permutations`], but in constant memory.
+///
+///# Examples
+/// ```rust
+/// # use distinct_permutations::distinct_permutations_iter;
+/// assert_eq!(distinct_permutations_iter(vec![0, 0, 1]).collect::<Vec<_>>(),
+///     vec![
+///         vec![0, 0, 1],
+///         vec![0, 1, 0],
+///         vec![1, 0, 0]
+/// ]);
+/// ```
+pub fn distinct_permutations_iter<T>(input: Vec<T>) -> DistinctPermutations<T>
+where
+    T: Ord + Clone,
+{
+    let mut input = input;
+    input.sort();
+    let current = if input.is_empty() { None } else { Some(input) };
+    DistinctPermutations { current }
+}
+
+/// Binomial coefficient `C(n, k)`, computed so every intermediate product stays
+/// exact (and as small as possible) to delay `u128` overflow.
+fn binomial(n: u128, k: u128) -> u128 {
+    let k = k.min(n - k);
+    let mut result = 1u128;
+    for i in 0..k {
+        result = result * (n - i) / (i + 1);
+    }
+    result
+}
+
+/// Number of distinct permutations of a multiset given its symbol counts, i.e.
+/// the multinomial coefficient `n! / ∏ c_k!`, evaluated as a product of
+/// binomials to keep intermediate values integral.
+fn multinomial(counts: &[usize]) -> u128 {
+    let mut result = 1u128;
+    let mut n = 0u128;
+    for &c in counts {
+        n += c as u128;
+        result *= binomial(n, c as u128);
+    }
+    result
+}
+
+/// Returns the number of distinct permutations of `multiset` (the multinomial
+/// coefficient `n! / ∏ c_k!`), without enumerating them.
+///
+///# Examples
+/// ```rust
+/// # use distinct_permutations::count_distinct_permutations;
+/// assert_eq!(count_distinct_permutations(&[0, 0, 1, 1]), 6);
+/// ```
+pub fn count_distinct_permutations<T>(multiset: &[T]) -> u128
+where
+    T: Eq + Hash + Clone,
+{
+    let counts = multiset.iter().cloned().counts();
+    multinomial(&counts.into_values().collect_vec())
+}
+
+/// Returns the lexicographic rank (0-based) of `permutation` among the distinct
+/// permutations of its own multiset.
+///
+/// This is the inverse of [`unrank`]; together they let callers shard the search
+/// space without enumerating predecessors.
+///
+///# Examples
+/// ```rust
+/// # use distinct_permutations::rank;
+/// assert_eq!(rank(&[1, 0, 0]), 2);
+/// ```
+pub fn rank<T>(permutation: &[T]) -> u128
+where
+    T: Eq + Hash + Clone + Ord,
+{
+    let mut counts = Counts::from(permutation.iter().cloned().counts());
+    let mut rank = 0u128;
+    for value in permutation {
+        let mut keys = counts.keys();
+        keys.sort();
+        for key in keys {
+            if &key < value {
+                // Every distinct permutation starting with a lexicographically
+                // smaller symbol precedes this one.
+                counts.remove(&key);
+                rank += multinomial(&counts.counts.values().cloned().collect_vec());
+                counts.add(key);
+            }
+        }
+        counts.remove(value);
+    }
+    rank
+}
+
+/// Returns the `index`-th (0-based) distinct permutation of `multiset` in
+/// lexicographic order, jumping directly to it without enumerating predecessors.
+///
+/// Panics if `index` is not smaller than [`count_distinct_permutations`].
+///
+///# Examples
+/// ```rust
+/// # use distinct_permutations::unrank;
+/// assert_eq!(unrank(vec![0, 0, 1], 2), vec![1, 0, 0]);
+/// ```
+pub fn unrank<T>(multiset: Vec<T>, mut index: u128) -> Vec<T>
+where
+    T: Eq + Hash + Clone + Ord,
+{
+    let total_len = multiset.len();
+    let mut counts = Counts::from(multiset.into_iter().counts());
+    let mut result = Vec::with_capacity(total_len);
+    while !counts.is_empty() {
+        let mut keys = counts.keys();
+        keys.sort();
+        let mut fixed = false;
+        for value in keys {
+            counts.remove(&value);
+            let block = multinomial(&counts.counts.values().cloned().collect_vec());
+            if index < block {
+                result.push(value);
+                fixed = true;
+                break;
+            }
+            index -= block;
+            counts.add(value);
+        }
+        assert!(fixed, "index out of range for unrank");
+    }
+    // An empty multiset has exactly one (empty) permutation.
+    assert!(index == 0, "index out of range for unrank");
+    result
+}
+
+/// A node in the singly linked list backing the cool-lex generator.
+struct CoolLexNode<T> {
+    value: T,
+    next: Option<usize>,
+}
+
+/// Lazy iterator visiting every distinct multiset permutation in cool-lex order,
+/// a Gray-code-like ordering where each successive permutation is obtained from
+/// its predecessor by a single prefix shift (rotating the last element of a
+/// prefix to the front). Created by [`distinct_permutations_coollex`].
+///
+/// This is Williams' loopless prefix-shift algorithm over a singly linked list
+/// of value nodes, stored here in an arena so the links are plain indices.
+pub struct CoolLexPermutations<T>
+where
+    T: Ord + Clone,
+{
+    nodes: Vec<CoolLexNode<T>>,
+    h: usize,
+    i: usize,
+    afteri: usize,
+    started: bool,
+    done: bool,
+}
+
+impl<T> CoolLexPermutations<T>
+where
+    T: Ord + Clone,
+{
+    /// Collect the list into a `Vec`, starting at the head node `h`.
+    fn read(&self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.nodes.len());
+        let mut cur = Some(self.h);
+        while let Some(idx) = cur {
+            result.push(self.nodes[idx].value.clone());
+            cur = self.nodes[idx].next;
+        }
+        result
+    }
+}
+
+impl<T> Iterator for CoolLexPermutations<T>
+where
+    T: Ord + Clone,
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            return Some(self.read());
+        }
+        // Lists of fewer than two nodes have a single permutation, already emitted.
+        if self.nodes.len() < 2 {
+            self.done = true;
+            return None;
+        }
+        let afteri_next = self.nodes[self.afteri].next;
+        let continues =
+            afteri_next.is_some() || self.nodes[self.afteri].value < self.nodes[self.h].value;
+        if !continues {
+            self.done = true;
+            return None;
+        }
+        let beforek = match afteri_next {
+            Some(next) if self.nodes[self.i].value >= self.nodes[next].value => self.afteri,
+            _ => self.i,
+        };
+        let k = self.nodes[beforek].next.expect("beforek has a successor");
+        self.nodes[beforek].next = self.nodes[k].next;
+        self.nodes[k].next = Some(self.h);
+        if self.nodes[k].value < self.nodes[self.h].value {
+            self.i = k;
+        }
+        self.afteri = self.nodes[self.i].next.expect("i is not the last node");
+        self.h = k;
+        Some(self.read())
+    }
+}
+
+/// Returns a [`CoolLexPermutations`] iterator over the distinct permutations of
+/// `input` in cool-lex (minimal-change) order. Every step is a prefix shift, so
+/// re-scoring an incremental change between successive permutations is cheap.
+///
+///# Examples
+/// ```rust
+/// # use distinct_permutations::{distinct_permutations, distinct_permutations_coollex};
+/// use itertools::Itertools;
+/// let mut coollex = distinct_permutations_coollex(vec![0, 0, 1]).collect_vec();
+/// coollex.sort();
+/// assert_eq!(coollex, distinct_permutations(vec![0, 0, 1]));
+/// ```
+pub fn distinct_permutations_coollex<T>(input: Vec<T>) -> CoolLexPermutations<T>
+where
+    T: Ord + Clone,
+{
+    let mut input = input;
+    // The first permutation is the multiset sorted in non-increasing order.
+    input.sort_by(|a, b| b.cmp(a));
+    let len = input.len();
+    let nodes = input
+        .into_iter()
+        .enumerate()
+        .map(|(idx, value)| CoolLexNode {
+            value,
+            next: if idx + 1 < len { Some(idx + 1) } else { None },
+        })
+        .collect_vec();
+    // `i` is the second-to-last node, `afteri` the last; both are unused when
+    // the list is too short to advance (guarded in `next`).
+    let i = len.saturating_sub(2);
+    let afteri = len.saturating_sub(1);
+    CoolLexPermutations {
+        nodes,
+        h: 0,
+        i,
+        afteri,
+        started: false,
+        done: len == 0,
+    }
+}
+
+/// In-place lexicographic stepping over the distinct permutations of a slice,
+/// the primitive behind [`DistinctPermutations`]. Because equal neighbours are
+/// never swapped across each other, repeated calls enumerate exactly the
+/// distinct permutations, letting callers drive enumeration over their own
+/// buffers with zero allocation.
+pub trait DistinctPermutationExt {
+    /// Rearranges the slice into its lexicographic successor distinct
+    /// permutation, returning `false` (and leaving it unchanged) when it is
+    /// already the last one.
+    fn next_distinct_permutation(&mut self) -> bool;
+
+    /// Rearranges the slice into its lexicographic predecessor distinct
+    /// permutation, returning `false` (and leaving it unchanged) when it is
+    /// already the first one.
+    fn prev_distinct_permutation(&mut self) -> bool;
+}
+
+impl<T> DistinctPermutationExt for [T]
+where
+    T: Ord,
+{
+    fn next_distinct_permutation(&mut self) -> bool {
+        if self.len() < 2 {
+            return false;
+        }
+        // Largest `i` with self[i] < self[i + 1].
+        let mut i = self.len() - 1;
+        while i > 0 && self[i - 1] >= self[i] {
+            i -= 1;
+        }
+        if i == 0 {
+            return false;
+        }
+        i -= 1;
+        // Largest `j > i` with self[j] > self[i].
+        let mut j = self.len() - 1;
+        while self[j] <= self[i] {
+            j -= 1;
+        }
+        self.swap(i, j);
+        self[i + 1..].reverse();
+        true
+    }
+
+    fn prev_distinct_permutation(&mut self) -> bool {
+        if self.len() < 2 {
+            return false;
+        }
+        // Largest `i` with self[i] > self[i + 1].
+        let mut i = self.len() - 1;
+        while i > 0 && self[i - 1] <= self[i] {
+            i -= 1;
+        }
+        if i == 0 {
+            return false;
+        }
+        i -= 1;
+        // Largest `j > i` with self[j] < self[i].
+        let mut j = self.len() - 1;
+        while self[j] >= self[i] {
+            j -= 1;
+        }
+        self.swap(i, j);
+        self[i + 1..].reverse();
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +663,144 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_iter_matches_eager() {
+        for input in [
+            vec![],
+            vec![0],
+            vec![0, 1, 2],
+            vec![0, 0, 1, 1],
+            vec![0, 0, 0, 1, 1, 2],
+        ] {
+            assert_eq!(
+                distinct_permutations_iter(input.clone()).collect_vec(),
+                distinct_permutations(input),
+            );
+        }
+    }
+
+    #[test]
+    fn test_iter_is_lazy() {
+        // Early termination must not require enumerating the whole space.
+        let first_two = distinct_permutations_iter(vec![0, 0, 1, 1])
+            .take(2)
+            .collect_vec();
+        assert_eq!(first_two, vec![vec![0, 0, 1, 1], vec![0, 1, 0, 1]]);
+    }
+
+    #[test]
+    fn test_coollex_same_set() {
+        for input in [
+            vec![0],
+            vec![0, 1, 2],
+            vec![0, 0, 1, 1],
+            vec![0, 0, 0, 1, 1, 2],
+        ] {
+            let mut coollex = distinct_permutations_coollex(input.clone()).collect_vec();
+            coollex.sort();
+            assert_eq!(coollex, distinct_permutations(input));
+        }
+    }
+
+    #[test]
+    fn test_coollex_is_prefix_shift() {
+        // Each successive permutation is the previous one with the last element
+        // of some prefix rotated to the front.
+        let perms = distinct_permutations_coollex(vec![0, 0, 1, 1, 2]).collect_vec();
+        for pair in perms.windows(2) {
+            let (prev, next) = (&pair[0], &pair[1]);
+            let shift = (1..=prev.len()).find(|&p| {
+                let mut rotated = prev.clone();
+                rotated[..p].rotate_right(1);
+                &rotated == next
+            });
+            assert!(shift.is_some(), "{prev:?} -> {next:?} is not a prefix shift");
+        }
+    }
+
+    #[test]
+    fn test_count_matches_enumeration() {
+        for input in [vec![0, 1, 2], vec![0, 0, 1, 1], vec![0, 0, 0, 1, 1, 2]] {
+            assert_eq!(
+                count_distinct_permutations(&input),
+                distinct_permutations(input.clone()).len() as u128,
+            );
+        }
+    }
+
+    #[test]
+    fn test_rank_unrank_roundtrip() {
+        let multiset = vec![0, 0, 1, 1, 2];
+        let all = distinct_permutations(multiset.clone());
+        for (i, perm) in all.iter().enumerate() {
+            let i = i as u128;
+            assert_eq!(rank(perm), i);
+            assert_eq!(&unrank(multiset.clone(), i), perm);
+        }
+    }
+
+    #[test]
+    fn test_k_partial() {
+        assert_eq!(
+            distinct_permutations_k(vec![0, 0, 1], 2),
+            vec![vec![0, 0], vec![0, 1], vec![1, 0]]
+        );
+        // k == 0 yields the single empty arrangement, like itertools.
+        assert_eq!(distinct_permutations_k(vec![0, 1], 0), vec![Vec::<u64>::new()]);
+        // k larger than the input has no arrangements.
+        assert_eq!(
+            distinct_permutations_k(vec![0, 1], 3),
+            Vec::<Vec<u64>>::new()
+        );
+        // Full length matches the front door.
+        assert_eq!(
+            distinct_permutations_k(vec![0, 0, 1, 1], 4),
+            distinct_permutations(vec![0, 0, 1, 1])
+        );
+    }
+
+    #[test]
+    fn test_k_iter_matches_eager() {
+        for (input, k) in [
+            (vec![0, 0, 1], 2usize),
+            (vec![0, 1, 2], 2),
+            (vec![0, 0, 1, 1], 3),
+            (vec![0, 1], 0),
+            (vec![0, 1], 3),
+        ] {
+            assert_eq!(
+                distinct_permutations_k_iter(input.clone(), k).collect_vec(),
+                distinct_permutations_k(input, k),
+            );
+        }
+    }
+
+    #[test]
+    fn test_slice_ext_enumerates_distinct() {
+        let mut buffer = vec![0, 0, 1, 1];
+        let mut seen = vec![buffer.clone()];
+        while buffer.next_distinct_permutation() {
+            seen.push(buffer.clone());
+        }
+        assert_eq!(seen, distinct_permutations(vec![0, 0, 1, 1]));
+
+        // `prev` walks the same sequence backwards.
+        let mut backwards = vec![buffer.clone()];
+        while buffer.prev_distinct_permutation() {
+            backwards.push(buffer.clone());
+        }
+        backwards.reverse();
+        assert_eq!(backwards, seen);
+    }
+
+    #[test]
+    fn test_slice_ext_boundaries() {
+        assert!(![1, 1, 0, 0].next_distinct_permutation());
+        assert!(![0, 0, 1, 1].prev_distinct_permutation());
+        assert!(![0u64; 0].next_distinct_permutation());
+        assert!(![0u64].prev_distinct_permutation());
+    }
+
     /// Output be identical to regular permutations for unique elements
     #[test]
     fn test_larger_unique() {